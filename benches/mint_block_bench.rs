@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rust_challenge::Blockchain;
+
+// Seeds thousands of unrelated accounts and a batch of non-conflicting transfers between them,
+// then measures how long mint_block takes to apply them in parallel
+const ACCOUNT_COUNT: usize = 4_000;
+
+fn seed_blockchain() -> Blockchain {
+    // Use an in-memory database so the benchmark measures batching/locking overhead, not disk I/O
+    let mut blockchain = Blockchain::new(":memory:").expect("failed to open in-memory db");
+    let mut secret_keys = Vec::with_capacity(ACCOUNT_COUNT);
+    for _ in 0..ACCOUNT_COUNT {
+        let (_, secret_key) = blockchain.create_account_with_new_keypair(1_000);
+        secret_keys.push(secret_key);
+    }
+
+    // Pair accounts up so every transfer touches two accounts no other transfer touches
+    for pair in secret_keys.chunks(2) {
+        if let [from, to] = pair {
+            let from_secret_hex = hex::encode(from.to_bytes());
+            let to_id = hex::encode(to.verifying_key().to_bytes());
+            let recent_block_hash = blockchain.current_recent_block_hash();
+            blockchain
+                .transfer(&from_secret_hex, to_id, 1, recent_block_hash)
+                .expect("seeded transfer should be valid");
+        }
+    }
+
+    blockchain
+}
+
+fn bench_mint_block(c: &mut Criterion) {
+    c.bench_function("mint_block_parallel_non_conflicting", |b| {
+        b.iter_batched(
+            seed_blockchain,
+            |mut blockchain| blockchain.mint_block().expect("mint_block should succeed in the benchmark"),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_mint_block);
+criterion_main!(benches);
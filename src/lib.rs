@@ -0,0 +1,831 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey}; // For signing and verifying transactions
+use num_bigint::BigUint; // For comparing block hashes against the difficulty target
+use rand::rngs::OsRng; // For keypair generation
+use rayon::prelude::*; // For parallelizing non-conflicting transaction batches
+use rusqlite::{params, Connection}; // For persisting blockchain state to SQLite
+use sha2::{Digest, Sha256}; // For hashing blocks
+use std::collections::{HashMap, HashSet, VecDeque}; // For storing accounts and tracking recent block hashes
+use std::sync::Mutex; // For per-account locking and thread-safe shared access
+use std::time::{Instant, SystemTime, UNIX_EPOCH}; // For handling time
+
+// Hash used as the previous_hash of the very first block in the chain
+pub const GENESIS_PREVIOUS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000";
+
+// Number of recent block hashes a transaction's recent_block_hash may reference before it expires
+pub const MAX_RECENT_BLOCK_HASHES: usize = 256;
+
+// Number of leading zero bits a freshly minted chain starts mining at
+pub const INITIAL_DIFFICULTY: u32 = 8;
+
+// Difficulty never retargets below this, so mining can't stall forever on a slow machine
+pub const MIN_DIFFICULTY: u32 = 1;
+
+// Difficulty never retargets above this, since SHA-256 only has 256 bits to spend
+pub const MAX_DIFFICULTY: u32 = 255;
+
+// Target number of seconds between mined blocks, matching the minting cadence in main.rs
+pub const TARGET_BLOCK_INTERVAL_SECS: u64 = 10;
+
+// Errors returned by account and transaction operations
+#[derive(Debug)]
+pub enum TransactionError {
+    InvalidSignature, // Signature doesn't verify, or the secret/public key was malformed
+    InsufficientFunds, // Sender doesn't have enough balance to cover the transfer
+    AccountNotFound,  // One of the referenced accounts doesn't exist
+    BlockHashExpired, // recent_block_hash has fallen out of the recent window
+    AlreadyProcessed, // A transaction with this signature was already confirmed (replay)
+    InvalidAmount,    // Transfer amount was not strictly positive
+}
+
+// Represents a user account with an ID and balance
+#[derive(Debug)]
+pub struct Account {
+    balance: Mutex<i64>, // Current balance of the account, locked independently so non-conflicting
+                          // transactions can be applied to different accounts in parallel
+}
+
+// Builds the canonical byte representation of a transfer, used for both signing and verification
+fn canonical_transaction_bytes(from: &str, to: &str, amount: i64, recent_block_hash: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(from.as_bytes());
+    bytes.extend_from_slice(to.as_bytes());
+    bytes.extend_from_slice(&amount.to_be_bytes());
+    bytes.extend_from_slice(recent_block_hash.as_bytes());
+    bytes
+}
+
+// Represents a transaction in the blockchain
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    from: Option<String>,      // Sender's account ID (None for system-generated transactions)
+    to: String,                // Receiver's account ID
+    amount: i64,                // Amount to transfer
+    signature: Vec<u8>,         // ed25519 signature over (from, to, amount, recent_block_hash); empty for system transactions
+    public_key: Vec<u8>,        // Sender's ed25519 public key bytes; empty for system transactions
+    recent_block_hash: String, // Hash this transaction was signed against, for replay/expiry checks
+}
+
+impl Transaction {
+    // The set of account IDs this transaction reads or writes, used to detect conflicts
+    // between transactions when building parallel-safe batches
+    fn touched_accounts(&self) -> HashSet<String> {
+        let mut touched = HashSet::new();
+        if let Some(from) = &self.from {
+            touched.insert(from.clone());
+        }
+        touched.insert(self.to.clone());
+        touched
+    }
+}
+
+// Represents a block in the blockchain
+#[derive(Debug)]
+pub struct Block {
+    transactions: Vec<Transaction>, // List of transactions in the block
+    timestamp: u64,                 // Time when the block was created
+    previous_hash: String,          // Hash of the previous block, chaining this block to it
+    hash: String,                   // SHA-256 hash of this block's own contents
+    nonce: u64,                     // Value tweaked during mining until the hash meets the difficulty target
+    difficulty: u32,                // Number of leading zero bits the hash had to meet when mined
+}
+
+impl Block {
+    // Computes this block's hash from its previous_hash, timestamp, transactions, and nonce
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.previous_hash.as_bytes());
+        hasher.update(self.timestamp.to_be_bytes());
+        for tx in &self.transactions {
+            hasher.update(tx.from.as_deref().unwrap_or("").as_bytes());
+            hasher.update(tx.to.as_bytes());
+            hasher.update(tx.amount.to_be_bytes());
+        }
+        hasher.update(self.nonce.to_be_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    // Increments the nonce and recomputes the hash until it meets the block's difficulty target
+    fn mine(&mut self) {
+        loop {
+            self.hash = self.compute_hash();
+            if meets_difficulty(&self.hash, self.difficulty) {
+                return;
+            }
+            self.nonce += 1;
+        }
+    }
+}
+
+// Returns true if `hash_hex`, read as a big-endian integer, is below 2^(256 - difficulty) —
+// i.e. it has at least `difficulty` leading zero bits
+fn meets_difficulty(hash_hex: &str, difficulty: u32) -> bool {
+    let Some(hash_value) = BigUint::parse_bytes(hash_hex.as_bytes(), 16) else {
+        return false;
+    };
+    let target = BigUint::from(1u8) << (256 - difficulty.min(256));
+    hash_value < target
+}
+
+// Groups transactions into batches where no two transactions in the same batch touch the
+// same account, so each batch can be applied in parallel without two threads racing on one
+// account's lock
+fn partition_into_batches(transactions: Vec<Transaction>) -> Vec<Vec<Transaction>> {
+    let mut batches: Vec<Vec<Transaction>> = Vec::new();
+    let mut batch_accounts: Vec<HashSet<String>> = Vec::new();
+
+    'tx: for tx in transactions {
+        let touched = tx.touched_accounts();
+        for (batch, accounts) in batches.iter_mut().zip(batch_accounts.iter_mut()) {
+            if accounts.is_disjoint(&touched) {
+                accounts.extend(touched);
+                batch.push(tx);
+                continue 'tx;
+            }
+        }
+        batch_accounts.push(touched);
+        batches.push(vec![tx]);
+    }
+
+    batches
+}
+
+// Represents the entire blockchain
+pub struct Blockchain {
+    db: Connection,                     // SQLite connection backing persistence of accounts and chain
+    accounts: HashMap<String, Account>, // Map of account IDs to Account structs
+    chain: Vec<Block>,                  // List of all blocks in the blockchain
+    pending_transactions: Vec<Transaction>, // Transactions waiting to be included in a block
+    recent_block_hashes: VecDeque<String>, // Bounded window of recent block hashes, newest at the back
+    processed_signatures: HashMap<String, HashSet<Vec<u8>>>, // Signatures already confirmed, keyed by the block hash they were confirmed in
+    difficulty: u32, // Current proof-of-work difficulty (leading zero bits), retargeted after every block
+}
+
+impl Blockchain {
+    // Opens (or creates) the SQLite database at `db_path`, reloading any accounts and blocks
+    // already persisted there
+    pub fn new(db_path: &str) -> rusqlite::Result<Self> {
+        let db = Connection::open(db_path)?;
+        Self::init_schema(&db)?;
+
+        let accounts = Self::load_accounts(&db)?;
+        let chain = Self::load_chain(&db)?;
+        let (recent_block_hashes, processed_signatures) = Self::rebuild_recent_window(&chain);
+        let difficulty = chain.last().map(|block| block.difficulty).unwrap_or(INITIAL_DIFFICULTY);
+
+        Ok(Self {
+            db,
+            accounts,
+            chain,
+            pending_transactions: Vec::new(),
+            recent_block_hashes,
+            processed_signatures,
+            difficulty,
+        })
+    }
+
+    // Creates the accounts/blocks/transactions tables if they don't already exist
+    fn init_schema(db: &Connection) -> rusqlite::Result<()> {
+        db.execute_batch(
+            "CREATE TABLE IF NOT EXISTS accounts (
+                id TEXT PRIMARY KEY,
+                balance INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS blocks (
+                idx INTEGER PRIMARY KEY,
+                timestamp INTEGER NOT NULL,
+                previous_hash TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                nonce INTEGER NOT NULL,
+                difficulty INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS transactions (
+                block_idx INTEGER NOT NULL REFERENCES blocks(idx),
+                position INTEGER NOT NULL,
+                from_id TEXT,
+                to_id TEXT NOT NULL,
+                amount INTEGER NOT NULL,
+                signature BLOB NOT NULL,
+                public_key BLOB NOT NULL,
+                recent_block_hash TEXT NOT NULL,
+                PRIMARY KEY (block_idx, position)
+            );",
+        )
+    }
+
+    // Loads every persisted account into memory
+    fn load_accounts(db: &Connection) -> rusqlite::Result<HashMap<String, Account>> {
+        let mut statement = db.prepare("SELECT id, balance FROM accounts")?;
+        let rows = statement.query_map([], |row| {
+            let id: String = row.get(0)?;
+            let balance: i64 = row.get(1)?;
+            Ok((id, Account { balance: Mutex::new(balance) }))
+        })?;
+        rows.collect()
+    }
+
+    // Loads every persisted block, along with its transactions, in chain order
+    fn load_chain(db: &Connection) -> rusqlite::Result<Vec<Block>> {
+        let mut block_statement = db.prepare(
+            "SELECT idx, timestamp, previous_hash, hash, nonce, difficulty FROM blocks ORDER BY idx",
+        )?;
+        let mut transaction_statement = db.prepare(
+            "SELECT from_id, to_id, amount, signature, public_key, recent_block_hash
+             FROM transactions WHERE block_idx = ?1 ORDER BY position",
+        )?;
+
+        let block_rows = block_statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, u64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, u64>(4)?,
+                    row.get::<_, u32>(5)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut chain = Vec::with_capacity(block_rows.len());
+        for (idx, timestamp, previous_hash, hash, nonce, difficulty) in block_rows {
+            let transactions = transaction_statement
+                .query_map(params![idx], |row| {
+                    Ok(Transaction {
+                        from: row.get(0)?,
+                        to: row.get(1)?,
+                        amount: row.get(2)?,
+                        signature: row.get(3)?,
+                        public_key: row.get(4)?,
+                        recent_block_hash: row.get(5)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            chain.push(Block { transactions, timestamp, previous_hash, hash, nonce, difficulty });
+        }
+        Ok(chain)
+    }
+
+    // Rebuilds the in-memory replay-protection window from the last MAX_RECENT_BLOCK_HASHES
+    // persisted blocks (or just the genesis hash if the chain is empty)
+    fn rebuild_recent_window(chain: &[Block]) -> (VecDeque<String>, HashMap<String, HashSet<Vec<u8>>>) {
+        let mut recent_block_hashes = VecDeque::new();
+        let mut processed_signatures = HashMap::new();
+
+        if chain.is_empty() {
+            recent_block_hashes.push_back(GENESIS_PREVIOUS_HASH.to_string());
+            processed_signatures.insert(GENESIS_PREVIOUS_HASH.to_string(), HashSet::new());
+            return (recent_block_hashes, processed_signatures);
+        }
+
+        let window_start = chain.len().saturating_sub(MAX_RECENT_BLOCK_HASHES);
+        for block in &chain[window_start..] {
+            let signatures = block
+                .transactions
+                .iter()
+                .filter(|tx| tx.from.is_some())
+                .map(|tx| tx.signature.clone())
+                .collect();
+            processed_signatures.insert(block.hash.clone(), signatures);
+            recent_block_hashes.push_back(block.hash.clone());
+        }
+        (recent_block_hashes, processed_signatures)
+    }
+
+    // Returns true if a transaction with this signature has already been confirmed in a
+    // block whose hash is still within the recent window
+    fn is_signature_processed(&self, signature: &[u8]) -> bool {
+        self.processed_signatures
+            .values()
+            .any(|signatures| signatures.contains(signature))
+    }
+
+    // Creates an account from an existing ed25519 public key (hex-encoded), writing it through
+    // to the database
+    pub fn create_account(&mut self, id: String, balance: i64) {
+        if self.accounts.contains_key(&id) {
+            println!("Account already exists!"); // Error if the account already exists
+        } else if let Err(err) = self
+            .db
+            .execute("INSERT INTO accounts (id, balance) VALUES (?1, ?2)", params![id, balance])
+        {
+            println!("Failed to persist account {}: {}", id, err);
+        } else {
+            self.accounts.insert(id.clone(), Account { balance: Mutex::new(balance) });
+            println!("Account created successfully with id: {}", id); // Success message
+        }
+    }
+
+    // Generates a new ed25519 keypair, creates an account for its public key, and returns the
+    // keypair so the caller can hand the secret key back to whoever controls the account
+    pub fn create_account_with_new_keypair(&mut self, balance: i64) -> (String, SigningKey) {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let id = hex::encode(signing_key.verifying_key().to_bytes());
+        self.create_account(id.clone(), balance);
+        (id, signing_key)
+    }
+
+    // Returns the most recent block hash transactions should be signed against. Signing against
+    // a hash that later falls out of the recent window (MAX_RECENT_BLOCK_HASHES blocks old) is
+    // what makes a transaction expire instead of being replayable indefinitely.
+    pub fn current_recent_block_hash(&self) -> String {
+        self.recent_block_hashes
+            .back()
+            .cloned()
+            .unwrap_or_else(|| GENESIS_PREVIOUS_HASH.to_string())
+    }
+
+    // Queues a signed transaction to transfer funds between accounts. `recent_block_hash` is
+    // whatever current_recent_block_hash() returned when the caller signed the transfer; if it's
+    // fallen out of the recent window by the time this is called, the transfer is rejected as
+    // expired rather than silently accepted against a stale reference.
+    pub fn transfer(
+        &mut self,
+        from_secret_hex: &str,
+        to: String,
+        amount: i64,
+        recent_block_hash: String,
+    ) -> Result<(), TransactionError> {
+        if amount <= 0 {
+            return Err(TransactionError::InvalidAmount);
+        }
+        if !self.recent_block_hashes.contains(&recent_block_hash) {
+            return Err(TransactionError::BlockHashExpired);
+        }
+
+        let secret_bytes: [u8; 32] = hex::decode(from_secret_hex)
+            .map_err(|_| TransactionError::InvalidSignature)?
+            .try_into()
+            .map_err(|_| TransactionError::InvalidSignature)?;
+        let signing_key = SigningKey::from_bytes(&secret_bytes);
+        let from = hex::encode(signing_key.verifying_key().to_bytes());
+
+        let from_account = self
+            .accounts
+            .get(&from)
+            .ok_or(TransactionError::AccountNotFound)?;
+        if *from_account.balance.lock().unwrap() < amount {
+            return Err(TransactionError::InsufficientFunds);
+        }
+        if !self.accounts.contains_key(&to) {
+            return Err(TransactionError::AccountNotFound);
+        }
+
+        let message = canonical_transaction_bytes(&from, &to, amount, &recent_block_hash);
+        let signature = signing_key.sign(&message);
+        let signature_bytes = signature.to_bytes().to_vec();
+
+        if self.is_signature_processed(&signature_bytes) {
+            return Err(TransactionError::AlreadyProcessed);
+        }
+        if self.pending_transactions.iter().any(|tx| tx.signature == signature_bytes) {
+            return Err(TransactionError::AlreadyProcessed);
+        }
+
+        self.pending_transactions.push(Transaction {
+            from: Some(from),
+            to,
+            amount,
+            signature: signature_bytes,
+            public_key: signing_key.verifying_key().to_bytes().to_vec(),
+            recent_block_hash,
+        });
+        println!("Transaction queued and will be confirmed in the next block.");
+        Ok(())
+    }
+
+    // Retrieves and displays the balance of a specific account
+    pub fn get_balance(&self, id: &String) {
+        match self.accounts.get(id) {
+            Some(account) => println!("Balance of {}: {}", id, *account.balance.lock().unwrap()), // Display balance
+            None => println!("Account not found."), // Error for nonexistent account
+        }
+    }
+
+    // Mints a new block: partitions pending transactions into batches with no overlapping
+    // accounts, then applies each batch's transactions in parallel with rayon. Returns the
+    // database error if persisting the new block fails, leaving it to the caller to decide how
+    // to respond instead of panicking inside the library.
+    pub fn mint_block(&mut self) -> rusqlite::Result<()> {
+        if !self.pending_transactions.is_empty() {
+            let previous_hash = self
+                .chain
+                .last()
+                .map(|block| block.hash.clone())
+                .unwrap_or_else(|| GENESIS_PREVIOUS_HASH.to_string());
+
+            let transactions: Vec<Transaction> = self.pending_transactions.drain(..).collect();
+            let batches = partition_into_batches(transactions);
+
+            let accounts = &self.accounts;
+            let recent_block_hashes = &self.recent_block_hashes;
+            let processed_signatures = &self.processed_signatures;
+            let confirmed_signatures: Mutex<HashSet<Vec<u8>>> = Mutex::new(HashSet::new());
+            let mut ordered_transactions = Vec::new();
+
+            for batch in batches {
+                // Only transactions that actually pass verification become part of the block;
+                // a rejected transaction must never be hashed/persisted as if it were confirmed.
+                let accepted: Vec<Option<Transaction>> = batch
+                    .into_par_iter()
+                    .map(|tx| {
+                        if let Some(from) = &tx.from {
+                            if !Self::verify_transaction_signature(&tx) {
+                                println!("Skipping transaction from {}: invalid signature.", from);
+                                return None;
+                            }
+                            if !recent_block_hashes.contains(&tx.recent_block_hash) {
+                                println!("Skipping transaction from {}: recent_block_hash expired.", from);
+                                return None;
+                            }
+                            let already_processed = processed_signatures
+                                .values()
+                                .any(|signatures| signatures.contains(&tx.signature))
+                                || confirmed_signatures.lock().unwrap().contains(&tx.signature);
+                            if already_processed {
+                                println!("Skipping transaction from {}: already processed (replay).", from);
+                                return None;
+                            }
+                            let from_account = accounts.get(from).unwrap();
+                            *from_account.balance.lock().unwrap() -= tx.amount; // Deduct amount from sender
+                            confirmed_signatures.lock().unwrap().insert(tx.signature.clone());
+                        }
+                        let to_account = accounts.get(&tx.to).unwrap();
+                        *to_account.balance.lock().unwrap() += tx.amount; // Add amount to receiver
+                        Some(tx)
+                    })
+                    .collect();
+                ordered_transactions.extend(accepted.into_iter().flatten());
+            }
+
+            let mut block = Block {
+                transactions: ordered_transactions,
+                timestamp: current_timestamp(), // Use current time as block timestamp
+                previous_hash,
+                hash: String::new(),     // Filled in by mine() once a valid nonce is found
+                nonce: 0,
+                difficulty: self.difficulty,
+            };
+            let mining_started = Instant::now();
+            block.mine(); // Search for a nonce whose hash meets the difficulty target
+            let mining_elapsed_secs = mining_started.elapsed().as_secs();
+
+            self.retarget_difficulty(mining_elapsed_secs);
+
+            // Persist before committing the block to any in-memory state that a caller could
+            // observe, so a failure here can't leave the chain/recent-hash window ahead of what's
+            // actually on disk.
+            self.persist_block(&block)?;
+
+            self.processed_signatures
+                .insert(block.hash.clone(), confirmed_signatures.into_inner().unwrap());
+            self.recent_block_hashes.push_back(block.hash.clone());
+            if self.recent_block_hashes.len() > MAX_RECENT_BLOCK_HASHES {
+                if let Some(evicted) = self.recent_block_hashes.pop_front() {
+                    self.processed_signatures.remove(&evicted); // Bound memory: drop signatures for hashes outside the window
+                }
+            }
+            self.chain.push(block); // Add the new block to the chain
+            println!("New block minted with confirmed transactions.");
+        } else {
+            println!("No transactions to confirm. Skipping block minting.");
+        }
+        Ok(())
+    }
+
+    // Adjusts the mining difficulty for the next block based on how long the just-mined block
+    // actually took to mine relative to TARGET_BLOCK_INTERVAL_SECS: faster than target tightens
+    // it, slower loosens it. Uses the measured mining duration rather than block timestamps,
+    // since the latter also include whatever idle time the caller waits between blocks.
+    fn retarget_difficulty(&mut self, mining_elapsed_secs: u64) {
+        if mining_elapsed_secs < TARGET_BLOCK_INTERVAL_SECS / 2 {
+            self.difficulty = (self.difficulty + 1).min(MAX_DIFFICULTY);
+        } else if mining_elapsed_secs > TARGET_BLOCK_INTERVAL_SECS * 2 {
+            self.difficulty = self.difficulty.saturating_sub(1).max(MIN_DIFFICULTY);
+        }
+    }
+
+    // Writes a newly minted block, its transactions, and the account balances it touched to
+    // the database inside a single transaction
+    fn persist_block(&self, block: &Block) -> rusqlite::Result<()> {
+        let idx = self.chain.len() as i64;
+        let mut touched_accounts = HashSet::new();
+
+        let tx = self
+            .db
+            .unchecked_transaction()
+            .expect("blockchain database connection does not support transactions");
+        tx.execute(
+            "INSERT INTO blocks (idx, timestamp, previous_hash, hash, nonce, difficulty) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                idx,
+                block.timestamp as i64,
+                block.previous_hash,
+                block.hash,
+                block.nonce as i64,
+                block.difficulty,
+            ],
+        )?;
+        for (position, transaction) in block.transactions.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO transactions
+                    (block_idx, position, from_id, to_id, amount, signature, public_key, recent_block_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    idx,
+                    position as i64,
+                    transaction.from,
+                    transaction.to,
+                    transaction.amount,
+                    transaction.signature,
+                    transaction.public_key,
+                    transaction.recent_block_hash,
+                ],
+            )?;
+            if let Some(from) = &transaction.from {
+                touched_accounts.insert(from.clone());
+            }
+            touched_accounts.insert(transaction.to.clone());
+        }
+        for id in touched_accounts {
+            if let Some(account) = self.accounts.get(&id) {
+                tx.execute(
+                    "UPDATE accounts SET balance = ?1 WHERE id = ?2",
+                    params![*account.balance.lock().unwrap(), id],
+                )?;
+            }
+        }
+        tx.commit()
+    }
+
+    // Verifies that a transaction's signature was produced by the claimed sender's public key
+    fn verify_transaction_signature(tx: &Transaction) -> bool {
+        let Some(from) = &tx.from else {
+            return true; // System-generated transactions carry no signature
+        };
+        let Ok(public_key_bytes) = <[u8; 32]>::try_from(tx.public_key.as_slice()) else {
+            return false;
+        };
+        if *from != hex::encode(public_key_bytes) {
+            return false; // public_key doesn't actually belong to the claimed sender
+        }
+        let Ok(verifying_key) = VerifyingKey::from_bytes(&public_key_bytes) else {
+            return false;
+        };
+        let Ok(signature_bytes) = <[u8; 64]>::try_from(tx.signature.as_slice()) else {
+            return false;
+        };
+        let signature = Signature::from_bytes(&signature_bytes);
+        let message = canonical_transaction_bytes(from, &tx.to, tx.amount, &tx.recent_block_hash);
+        verifying_key.verify(&message, &signature).is_ok()
+    }
+
+    // Walks the chain and verifies every block's hash and linkage to the previous block
+    pub fn validate_chain(&self) -> Result<(), String> {
+        let mut expected_previous_hash = GENESIS_PREVIOUS_HASH.to_string();
+        for (index, block) in self.chain.iter().enumerate() {
+            if block.previous_hash != expected_previous_hash {
+                return Err(format!(
+                    "Block {} has previous_hash {} but expected {}",
+                    index, block.previous_hash, expected_previous_hash
+                ));
+            }
+            if block.compute_hash() != block.hash {
+                return Err(format!("Block {} hash does not match its contents", index));
+            }
+            if !meets_difficulty(&block.hash, block.difficulty) {
+                return Err(format!(
+                    "Block {} does not meet its claimed difficulty of {}",
+                    index, block.difficulty
+                ));
+            }
+            expected_previous_hash = block.hash.clone();
+        }
+        Ok(())
+    }
+}
+
+// Returns the current timestamp in seconds since UNIX_EPOCH
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_chain() -> Blockchain {
+        Blockchain::new(":memory:").expect("in-memory db should always open")
+    }
+
+    #[test]
+    fn transfer_rejects_duplicate_pending_signature() {
+        let mut chain = test_chain();
+        let (_, from_key) = chain.create_account_with_new_keypair(100);
+        let (to_id, _) = chain.create_account_with_new_keypair(0);
+        let from_secret = hex::encode(from_key.to_bytes());
+
+        let recent_block_hash = chain.current_recent_block_hash();
+        chain
+            .transfer(&from_secret, to_id.clone(), 10, recent_block_hash.clone())
+            .expect("first transfer should queue");
+        let result = chain.transfer(&from_secret, to_id, 10, recent_block_hash);
+
+        assert!(matches!(result, Err(TransactionError::AlreadyProcessed)));
+    }
+
+    #[test]
+    fn mint_block_rejects_forged_signature() {
+        let mut chain = test_chain();
+        let (from_id, from_key) = chain.create_account_with_new_keypair(100);
+        let (to_id, _) = chain.create_account_with_new_keypair(0);
+        let from_secret = hex::encode(from_key.to_bytes());
+        let recent_block_hash = chain.current_recent_block_hash();
+        chain.transfer(&from_secret, to_id.clone(), 10, recent_block_hash).unwrap();
+
+        // Tamper with the queued transaction's signature after it's already been accepted.
+        chain.pending_transactions[0].signature[0] ^= 0xFF;
+        chain.mint_block().unwrap();
+
+        assert_eq!(*chain.accounts[&from_id].balance.lock().unwrap(), 100);
+        assert_eq!(*chain.accounts[&to_id].balance.lock().unwrap(), 0);
+        assert!(
+            chain.chain[0].transactions.is_empty(),
+            "a rejected transaction must not be recorded in the minted block"
+        );
+    }
+
+    #[test]
+    fn verify_transaction_signature_rejects_a_mismatched_claimed_sender() {
+        let chain = test_chain();
+        let victim_key = SigningKey::generate(&mut OsRng);
+        let attacker_key = SigningKey::generate(&mut OsRng);
+        let victim_id = hex::encode(victim_key.verifying_key().to_bytes());
+        let to_id = "recipient".to_string();
+
+        // Sign a legitimate transfer as the attacker, but claim it's from the victim while
+        // attaching the attacker's own public key. The signature verifies fine against that
+        // key; only checking `from == hex(public_key)` catches the mismatch.
+        let recent_block_hash = chain.current_recent_block_hash();
+        let message = canonical_transaction_bytes(&victim_id, &to_id, 10, &recent_block_hash);
+        let signature = attacker_key.sign(&message);
+
+        let forged = Transaction {
+            from: Some(victim_id),
+            to: to_id,
+            amount: 10,
+            signature: signature.to_bytes().to_vec(),
+            public_key: attacker_key.verifying_key().to_bytes().to_vec(),
+            recent_block_hash,
+        };
+
+        assert!(!Blockchain::verify_transaction_signature(&forged));
+    }
+
+    fn system_transaction(from: &str, to: &str) -> Transaction {
+        Transaction {
+            from: Some(from.to_string()),
+            to: to.to_string(),
+            amount: 1,
+            signature: Vec::new(),
+            public_key: Vec::new(),
+            recent_block_hash: GENESIS_PREVIOUS_HASH.to_string(),
+        }
+    }
+
+    #[test]
+    fn partition_into_batches_keeps_conflicting_transactions_apart() {
+        // "b" is touched by both transactions, so they can't be applied in the same batch
+        // without racing on the same account's lock.
+        let conflicting = vec![system_transaction("a", "b"), system_transaction("b", "c")];
+        let batches = partition_into_batches(conflicting);
+        assert_eq!(batches.len(), 2);
+
+        // "a" -> "b" and "c" -> "d" touch disjoint accounts, so they can share a batch.
+        let non_conflicting = vec![system_transaction("a", "b"), system_transaction("c", "d")];
+        let batches = partition_into_batches(non_conflicting);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 2);
+    }
+
+    #[test]
+    fn blockchain_state_survives_reopening_the_database() {
+        let path = std::env::temp_dir().join(format!("rust_challenge_test_{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let to_id = {
+            let mut chain = Blockchain::new(path).expect("failed to create test db");
+            let (_, from_key) = chain.create_account_with_new_keypair(100);
+            let (to_id, _) = chain.create_account_with_new_keypair(0);
+            let from_secret = hex::encode(from_key.to_bytes());
+            let recent_block_hash = chain.current_recent_block_hash();
+            chain.transfer(&from_secret, to_id.clone(), 10, recent_block_hash).unwrap();
+            chain.mint_block().unwrap();
+            to_id
+        };
+
+        let reopened = Blockchain::new(path).expect("failed to reopen test db");
+        reopened
+            .validate_chain()
+            .expect("reloaded chain should still validate");
+        assert_eq!(reopened.chain.len(), 1);
+        assert_eq!(*reopened.accounts[&to_id].balance.lock().unwrap(), 10);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn validate_chain_detects_a_tampered_hash() {
+        let mut chain = test_chain();
+        chain.create_account("a".to_string(), 10);
+        chain.create_account("b".to_string(), 0);
+        let recent_block_hash = chain.current_recent_block_hash();
+        chain.pending_transactions.push(Transaction {
+            from: None,
+            to: "b".to_string(),
+            amount: 1,
+            signature: Vec::new(),
+            public_key: Vec::new(),
+            recent_block_hash,
+        });
+        chain.mint_block().unwrap();
+        assert!(chain.validate_chain().is_ok());
+
+        chain.chain[0].hash.push('0');
+        assert!(chain.validate_chain().is_err());
+    }
+
+    #[test]
+    fn validate_chain_detects_a_broken_previous_hash_link() {
+        let mut chain = test_chain();
+        chain.create_account("a".to_string(), 10);
+        chain.create_account("b".to_string(), 0);
+        let recent_block_hash = chain.current_recent_block_hash();
+        chain.pending_transactions.push(Transaction {
+            from: None,
+            to: "b".to_string(),
+            amount: 1,
+            signature: Vec::new(),
+            public_key: Vec::new(),
+            recent_block_hash,
+        });
+        chain.mint_block().unwrap();
+        assert!(chain.validate_chain().is_ok());
+
+        chain.chain[0].previous_hash = "not the genesis hash".to_string();
+        assert!(chain.validate_chain().is_err());
+    }
+
+    #[test]
+    fn meets_difficulty_checks_leading_zero_bits() {
+        // A hash whose only guaranteed zero bits are its leading nibble meets a difficulty of 4
+        // (needs >= 4 leading zero bits) but not 8 (needs a whole leading zero byte).
+        let hash = format!("0{}", "f".repeat(63));
+        assert!(meets_difficulty(&hash, 4));
+        assert!(!meets_difficulty(&hash, 8));
+    }
+
+    #[test]
+    fn retarget_difficulty_increases_when_mining_is_fast() {
+        let mut chain = test_chain();
+        let starting = chain.difficulty;
+        chain.retarget_difficulty(0);
+        assert_eq!(chain.difficulty, starting + 1);
+    }
+
+    #[test]
+    fn retarget_difficulty_decreases_when_mining_is_slow() {
+        let mut chain = test_chain();
+        chain.difficulty = 5;
+        chain.retarget_difficulty(TARGET_BLOCK_INTERVAL_SECS * 3);
+        assert_eq!(chain.difficulty, 4);
+    }
+
+    #[test]
+    fn retarget_difficulty_holds_steady_near_target() {
+        let mut chain = test_chain();
+        chain.difficulty = 5;
+        chain.retarget_difficulty(TARGET_BLOCK_INTERVAL_SECS);
+        assert_eq!(chain.difficulty, 5);
+    }
+
+    #[test]
+    fn transfer_rejects_a_recent_block_hash_that_has_expired() {
+        let mut chain = test_chain();
+        let (_, from_key) = chain.create_account_with_new_keypair(100);
+        let (to_id, _) = chain.create_account_with_new_keypair(0);
+        let from_secret = hex::encode(from_key.to_bytes());
+        let stale_hash = chain.current_recent_block_hash();
+
+        // Simulate MAX_RECENT_BLOCK_HASHES blocks passing without this transfer being submitted,
+        // so `stale_hash` falls out of the recent window before the caller gets around to it.
+        chain.recent_block_hashes.clear();
+        chain.recent_block_hashes.push_back("0".repeat(64));
+
+        let result = chain.transfer(&from_secret, to_id, 10, stale_hash);
+        assert!(matches!(result, Err(TransactionError::BlockHashExpired)));
+    }
+}